@@ -0,0 +1,176 @@
+//! Cooperative cancellation for a single background future, via [`Group::attach_abortable`].
+//!
+//! [`Group::detach_and_cancel`] already lets a caller drop a specific attached future, but only if
+//! they still have the group (and the future's [`Handle`](crate::Handle)) in hand. The types here
+//! instead let a caller cancel a future by signal, from anywhere that holds the returned
+//! [`AbortHandle`], without threading the group back to the cancellation site.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+#[cfg(feature = "alloc")]
+use alloc::sync::Arc;
+
+/// The error an [`Abortable`] future resolves to when it is canceled via its [`AbortHandle`] before
+/// the future it wraps completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Aborted;
+
+/// The cooperative cancellation flag shared between an [`AbortHandle`] and the [`Abortable`] future
+/// it guards.
+///
+/// Without the `alloc` feature, [`Group::attach_abortable`](crate::Group::attach_abortable) has
+/// nowhere to allocate a flag of its own, so the caller constructs one and passes a reference to it;
+/// with `alloc`, the flag is allocated internally and this type is only used through the `alloc`
+/// [`AbortHandle`], which owns it via an `Arc`.
+#[derive(Debug, Default)]
+pub struct AbortFlag {
+    aborted: AtomicBool,
+    // `spin::Mutex` rather than an `alloc`-only primitive: the non-`alloc` version of this flag is
+    // caller-allocated stack storage, so the waker slot must work without an allocator too. This is
+    // the crate's first dependency besides `pin-project-lite`: `forbid(unsafe_code)` rules out a
+    // hand-rolled lock-free cell for a non-`Copy`, `Sync` value like `Waker` (no safe way to swap a
+    // `Box<Waker>` through an `AtomicPtr` without `unsafe`), and `core`/`alloc` have no safe mutex.
+    waker: spin::Mutex<Option<Waker>>,
+}
+
+impl AbortFlag {
+    /// Constructs a new, not-yet-aborted flag.
+    pub const fn new() -> Self {
+        Self {
+            aborted: AtomicBool::new(false),
+            waker: spin::Mutex::new(None),
+        }
+    }
+
+    fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Acquire)
+    }
+
+    /// Records the waker the guarded future was most recently polled with, so [`abort`](Self::abort)
+    /// can wake the task driving it instead of only taking effect on its next unrelated wakeup.
+    fn register(&self, waker: &Waker) {
+        let mut slot = self.waker.lock();
+        if !slot.as_ref().is_some_and(|current| current.will_wake(waker)) {
+            *slot = Some(waker.clone());
+        }
+    }
+
+    fn abort(&self) {
+        self.aborted.store(true, Ordering::Release);
+        if let Some(waker) = self.waker.lock().take() {
+            waker.wake();
+        }
+    }
+}
+
+/// A cheap handle that cooperatively cancels the specific background future it was returned
+/// alongside by [`Group::attach_abortable`](crate::Group::attach_abortable). Calling
+/// [`abort`](Self::abort) causes the wrapped future to resolve to `Err(Aborted)` the next time the
+/// group polls it, instead of delegating to the future it wraps.
+///
+/// This version borrows the flag supplied to [`attach_abortable`](crate::Group::attach_abortable),
+/// so it cannot outlive that flag. See the `alloc` version of this type for an owned alternative.
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug, Clone, Copy)]
+pub struct AbortHandle<'flag> {
+    flag: &'flag AbortFlag,
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<'flag> AbortHandle<'flag> {
+    pub(crate) fn new(flag: &'flag AbortFlag) -> Self {
+        Self { flag }
+    }
+
+    /// Cancels the future this handle was returned alongside, waking the task currently driving the
+    /// group so it notices the cancellation on its own, rather than only on some unrelated wakeup.
+    pub fn abort(&self) {
+        self.flag.abort();
+    }
+
+    /// Reports whether [`abort`](Self::abort) has already been called.
+    pub fn is_aborted(&self) -> bool {
+        self.flag.is_aborted()
+    }
+}
+
+/// See the `not(feature = "alloc")` version of this type for documentation. This version shares its
+/// flag through an `Arc` rather than a borrow, so it can be stored, cloned, and moved independently
+/// of the group and flag it was returned alongside.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct AbortHandle {
+    flag: Arc<AbortFlag>,
+}
+
+#[cfg(feature = "alloc")]
+impl AbortHandle {
+    pub(crate) fn new(flag: Arc<AbortFlag>) -> Self {
+        Self { flag }
+    }
+
+    /// Cancels the future this handle was returned alongside, waking the task currently driving the
+    /// group so it notices the cancellation on its own, rather than only on some unrelated wakeup.
+    pub fn abort(&self) {
+        self.flag.abort();
+    }
+
+    /// Reports whether [`abort`](Self::abort) has already been called.
+    pub fn is_aborted(&self) -> bool {
+        self.flag.is_aborted()
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+pin_project_lite::pin_project! {
+    /// A future wrapper that resolves to `Err(Aborted)` once its [`AbortHandle`] cancels it, instead
+    /// of delegating to the wrapped future. Returned from
+    /// [`Group::attach_abortable`](crate::Group::attach_abortable).
+    pub struct Abortable<'flag, F> {
+        #[pin]
+        pub(crate) fut: F,
+        pub(crate) flag: &'flag AbortFlag,
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<F: Future> Future for Abortable<'_, F> {
+    type Output = Result<F::Output, Aborted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.flag.register(cx.waker());
+        if this.flag.is_aborted() {
+            return Poll::Ready(Err(Aborted));
+        }
+        this.fut.poll(cx).map(Ok)
+    }
+}
+
+#[cfg(feature = "alloc")]
+pin_project_lite::pin_project! {
+    /// See the `not(feature = "alloc")` version of this type for documentation. This version shares
+    /// its flag through an `Arc` rather than a borrow, matching the `alloc` [`AbortHandle`].
+    pub struct Abortable<F> {
+        #[pin]
+        pub(crate) fut: F,
+        pub(crate) flag: Arc<AbortFlag>,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future> Future for Abortable<F> {
+    type Output = Result<F::Output, Aborted>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        this.flag.register(cx.waker());
+        if this.flag.is_aborted() {
+            return Poll::Ready(Err(Aborted));
+        }
+        this.fut.poll(cx).map(Ok)
+    }
+}
@@ -0,0 +1,177 @@
+//! An `alloc`-backed dynamic group of type-erased background futures, gated behind the `alloc`
+//! feature.
+//!
+//! [`Group`](crate::Group)'s set of attached futures is fixed by its type, which makes it
+//! impossible to attach a future in only one branch of a conditional while keeping the group usable
+//! afterward. [`DynGroup`] trades that zero-allocation static typing for a `Vec`-backed list of
+//! boxed, type-erased futures that can be attached and detached from arbitrary control flow — a
+//! minimal [`FuturesUnordered`](https://docs.rs/futures/latest/futures/stream/struct.FuturesUnordered.html)
+//! specialized to this crate's background-future model.
+
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use crate::ReadyOrNot;
+
+/// A background future after it has been type-erased and pinned to the heap for storage in a
+/// [`DynGroup`].
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T>>>;
+
+/// A lightweight handle identifying a future attached to a [`DynGroup`]. Combines the future's slot
+/// index with a generation counter, so a ticket for a detached (and possibly reused) slot can never
+/// be confused with a different future later attached to the same slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ticket {
+    index: usize,
+    generation: u64,
+}
+
+struct Slot<T> {
+    generation: u64,
+    state: ReadyOrNot<BoxFuture<T>>,
+}
+
+/// An `alloc`-backed group of type-erased background futures, all sharing output type `T`.
+///
+/// Unlike [`Group`](crate::Group), a `DynGroup` is driven through `&mut self` rather than being
+/// consumed and reissued on every [`attach`](Self::attach)/[`detach`](Self::detach), so it remains
+/// usable across branches that only sometimes add a future.
+///
+/// # Example
+/// ```
+/// # use async_let::dyn_group::DynGroup;
+/// # pollster::block_on(async {
+/// let mut group = DynGroup::new();
+///
+/// let ticket = group.attach(async { 3 + 7 });
+///
+/// let output = group.wait_for(async { "driven while the background future runs" }).await;
+/// assert_eq!(output, "driven while the background future runs");
+///
+/// let result = group.detach(ticket).unwrap();
+/// assert_eq!(result.output().await, 10);
+/// # });
+/// ```
+pub struct DynGroup<T> {
+    slots: Vec<Option<Slot<T>>>,
+    free: Vec<usize>,
+    next_generation: u64,
+}
+
+impl<T> core::fmt::Debug for DynGroup<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        // `Slot` holds a boxed `dyn Future`, which isn't `Debug`, so report shape instead of contents.
+        f.debug_struct("DynGroup")
+            .field("slots", &self.slots.len())
+            .field("free", &self.free)
+            .field("next_generation", &self.next_generation)
+            .finish()
+    }
+}
+
+impl<T> DynGroup<T> {
+    /// Constructs a new, empty dynamic group.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            slots: Vec::new(),
+            free: Vec::new(),
+            next_generation: 0,
+        }
+    }
+
+    /// Attaches a future to this group, boxing and pinning it to the heap. Returns a [`Ticket`] that
+    /// can later be passed to [`detach`](Self::detach) to remove the future.
+    pub fn attach<F>(&mut self, fut: F) -> Ticket
+    where
+        F: Future<Output = T> + 'static,
+    {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        let slot = Slot {
+            generation,
+            state: ReadyOrNot::Not(Box::pin(fut)),
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(slot);
+                index
+            }
+            None => {
+                self.slots.push(Some(slot));
+                self.slots.len() - 1
+            }
+        };
+        Ticket { index, generation }
+    }
+
+    /// Removes the future identified by `ticket` from this group, returning it to the caller. The
+    /// returned [`ReadyOrNot`] holds the future's output if it had already completed, or the future
+    /// itself otherwise.
+    ///
+    /// Returns `None` if `ticket` does not identify a future currently in this group, which happens
+    /// if it was already detached.
+    pub fn detach(&mut self, ticket: Ticket) -> Option<ReadyOrNot<BoxFuture<T>>> {
+        let occupant = self.slots.get_mut(ticket.index)?.take()?;
+        if occupant.generation != ticket.generation {
+            // not the slot's current occupant; put it back and report no match.
+            self.slots[ticket.index] = Some(occupant);
+            return None;
+        }
+        self.free.push(ticket.index);
+        Some(occupant.state)
+    }
+
+    /// Polls every not-yet-ready future attached to this group once.
+    pub(crate) fn poll_once(&mut self, cx: &mut Context<'_>) {
+        for slot in self.slots.iter_mut().flatten() {
+            if let ReadyOrNot::Not(fut) = &mut slot.state {
+                if let Poll::Ready(val) = fut.as_mut().poll(cx) {
+                    slot.state = ReadyOrNot::Ready(val);
+                }
+            }
+        }
+    }
+
+    /// Await a future while concurrently driving this group's attached background futures, mirroring
+    /// [`Group::wait_for`](crate::Group::wait_for).
+    pub fn wait_for<F>(&mut self, fut: F) -> WaitFor<'_, F, T> {
+        WaitFor {
+            driving_fut: fut,
+            group: self,
+        }
+    }
+}
+
+impl<T> Default for DynGroup<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Future type for the [`DynGroup::wait_for`] method.
+    pub struct WaitFor<'group, F, T> {
+        #[pin]
+        driving_fut: F,
+        group: &'group mut DynGroup<T>,
+    }
+}
+
+impl<F: Future, T> Future for WaitFor<'_, F, T> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let poll = this.driving_fut.poll(cx);
+        if poll.is_pending() {
+            this.group.poll_once(cx);
+        }
+        poll
+    }
+}
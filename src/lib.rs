@@ -71,7 +71,8 @@
 //! - **Branching**: because the set of futures is statically tracked, it is not possible to attach a future in only one branch
 //!   of a condtional if one wishes the group to remain accessible after the conditional. Futures of different types may
 //!   be attached to the same location in a group by erasing the type of the attached future to `dyn Future<Output = X>`,
-//!   but this has its limitations.
+//!   but this has its limitations. The `alloc`-gated [`DynGroup`] trades away the static typing entirely in exchange for
+//!   support for this kind of arbitrary attachment and detachment.
 //!
 //! [`attach`]: Group::attach
 //! [`detach`]: Group::detach
@@ -80,14 +81,25 @@
 #![no_std]
 #![forbid(unsafe_code)]
 
-use core::{future::Future, marker::PhantomData};
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
-use list::{At, Detach, Empty, FutList};
-use wait::WaitFor;
+use core::{future::Future, marker::PhantomData, task::Context};
+
+use list::{At, Detach, Empty, FutList, Peek, Race};
+use wait::{WaitAll, WaitFor, WaitForAny};
 
 /// Types and traits for interacting with a group of futures.
+pub mod abort;
 pub mod list;
 pub mod wait;
+#[cfg(feature = "alloc")]
+pub mod dyn_group;
+#[cfg(feature = "alloc")]
+mod waker;
+
+#[cfg(feature = "alloc")]
+pub use dyn_group::DynGroup;
 
 /// A typed handle representing a specific future type in an async let group. A handle can be redeemed for the future
 /// it represents by passing it to [`Group::detach`].
@@ -97,6 +109,27 @@ pub struct Handle<F> {
     _ph: PhantomData<F>,
 }
 
+/// Return type of [`Group::attach_abortable`]: the usual handle and new group, alongside an
+/// [`AbortHandle`](abort::AbortHandle) for the newly attached future.
+#[cfg(not(feature = "alloc"))]
+pub type AttachAbortable<'flag, F, List> = (
+    Handle<abort::Abortable<'flag, F>>,
+    abort::AbortHandle<'flag>,
+    Group<At<abort::Abortable<'flag, F>, List>>,
+);
+
+/// See the `not(feature = "alloc")` version of this alias for documentation.
+#[cfg(feature = "alloc")]
+pub type AttachAbortable<F, List> = (
+    Handle<abort::Abortable<F>>,
+    abort::AbortHandle,
+    Group<At<abort::Abortable<F>, List>>,
+);
+
+/// Return type of [`Group::try_detach`]: the detached future's output and the new group on success,
+/// or the group unchanged (with the future still attached) if it wasn't ready yet.
+pub type TryDetach<F, List, Remaining> = Result<(<F as Future>::Output, Group<Remaining>), Group<List>>;
+
 /// This type holds a future that has been detached from a group.
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ReadyOrNot<F: Future>
@@ -126,7 +159,7 @@ impl<F: Future> ReadyOrNot<F>
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Group<List> {
     /// The type-safe list of futures held in this group.
-    fut_list: List,
+    pub(crate) fut_list: List,
 }
 
 impl Group<Empty> {
@@ -164,8 +197,30 @@ impl<List> Group<List> {
     /// let mut fut = Box::pin(some_future()); // pin to the heap
     /// let (handle, group) = group.attach(fut);
     /// ```
+    #[cfg(not(feature = "alloc"))]
+    pub fn attach<F: Future + Unpin>(self, fut: F) -> (Handle<F>, Group<At<F, List>>) {
+        (
+            Handle { _ph: PhantomData },
+            Group {
+                fut_list: At {
+                    node: ReadyOrNot::Not(fut),
+                    tail: self.fut_list,
+                    _holds_output: PhantomData,
+                },
+            },
+        )
+    }
+
+    /// See the `not(feature = "alloc")` version of this method for documentation. This version
+    /// additionally equips the attached future with a personal waker, so driving a large group only
+    /// re-polls the futures that were actually woken since the last poll.
+    #[cfg(feature = "alloc")]
     pub fn attach<F: Future + Unpin>(self, fut: F) -> (Handle<F>, Group<At<F, List>>)
+    where
+        List: list::ListBits + list::ListLen,
     {
+        let bits = self.fut_list.bits();
+        let index = List::LEN;
         (
             Handle { _ph: PhantomData },
             Group {
@@ -173,11 +228,80 @@ impl<List> Group<List> {
                     node: ReadyOrNot::Not(fut),
                     tail: self.fut_list,
                     _holds_output: PhantomData,
+                    wake: crate::waker::WakeTracking::new(bits, index),
                 },
             },
         )
     }
 
+    /// Adds a future to this group's set of background futures, wrapped so that it can be canceled
+    /// from outside the group by signal rather than by moving the group back to the cancellation
+    /// site (as [`detach_and_cancel`](Self::detach_and_cancel) requires).
+    ///
+    /// Returns the usual handle and new group alongside an [`AbortHandle`](abort::AbortHandle). Once
+    /// [`abort`](abort::AbortHandle::abort) is called, the wrapped future resolves to
+    /// `Err(Aborted)` the next time the group polls it, instead of delegating to the future it wraps.
+    ///
+    /// Because this crate has no allocator without the `alloc` feature, the caller must supply the
+    /// shared cancellation flag themselves and keep it alive at least as long as the returned
+    /// [`AbortHandle`](abort::AbortHandle).
+    /// ```
+    /// # use core::pin::pin;
+    /// # async fn some_future() {}
+    /// # pollster::block_on(async {
+    /// use async_let::abort::AbortFlag;
+    ///
+    /// let group = async_let::Group::new();
+    /// let flag = AbortFlag::new();
+    /// let fut = pin!(some_future());
+    /// let (handle, abort_handle, mut group) = group.attach_abortable(fut, &flag);
+    ///
+    /// abort_handle.abort();
+    /// let (result, _group) = group.detach_and_wait_for(handle).await;
+    /// assert!(result.is_err());
+    /// # });
+    /// ```
+    #[cfg(not(feature = "alloc"))]
+    pub fn attach_abortable<'flag, F: Future + Unpin>(
+        self,
+        fut: F,
+        flag: &'flag abort::AbortFlag,
+    ) -> AttachAbortable<'flag, F, List> {
+        let wrapped = abort::Abortable { fut, flag };
+        let (handle, group) = self.attach(wrapped);
+        (handle, abort::AbortHandle::new(flag), group)
+    }
+
+    /// See the `not(feature = "alloc")` version of this method for documentation. This version
+    /// allocates the shared cancellation flag internally, so the caller does not need to keep any
+    /// external storage alive for the returned [`AbortHandle`](abort::AbortHandle).
+    /// ```
+    /// # use core::pin::pin;
+    /// # async fn some_future() {}
+    /// # pollster::block_on(async {
+    /// let group = async_let::Group::new();
+    /// let fut = pin!(some_future());
+    /// let (handle, abort_handle, mut group) = group.attach_abortable(fut);
+    ///
+    /// abort_handle.abort();
+    /// let (result, _group) = group.detach_and_wait_for(handle).await;
+    /// assert!(result.is_err());
+    /// # });
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn attach_abortable<F: Future + Unpin>(self, fut: F) -> AttachAbortable<F, List>
+    where
+        List: list::ListBits + list::ListLen,
+    {
+        let flag = alloc::sync::Arc::new(abort::AbortFlag::new());
+        let wrapped = abort::Abortable {
+            fut,
+            flag: flag.clone(),
+        };
+        let (handle, group) = self.attach(wrapped);
+        (handle, abort::AbortHandle::new(flag), group)
+    }
+
     /// Removes a future from this group's set of background futures. The future held by this group
     /// is relinquished and returned to the caller. The detached future may have been partially driven or even completed.
     /// If the future is already completed, then its output is saved and returned to the caller instead of the future.
@@ -336,4 +460,180 @@ impl<List> Group<List> {
         let (_, group) = self.detach(handle);
         group
     }
+
+    /// Consumes this group and returns a future that resolves once every attached background future
+    /// has completed, collecting their outputs into a nested tuple ordered from the least recently
+    /// attached future to the most recently attached (i.e. attach order).
+    ///
+    /// This is a concurrent join over the group's background futures: unlike [`wait_for`], it does not
+    /// take an additional future to drive, and it polls every not-yet-ready node on each wakeup until
+    /// all of them have resolved.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::pin::pin;
+    /// # pollster::block_on(async {
+    /// let group = async_let::Group::new();
+    ///
+    /// let f1 = pin!(async { 3 });
+    /// let (_, group) = group.attach(f1);
+    /// let f2 = pin!(async { 7 });
+    /// let (_, group) = group.attach(f2);
+    ///
+    /// let ((_, first), second) = group.wait_all().await;
+    /// assert_eq!((first, second), (3, 7));
+    /// # });
+    /// ```
+    /// [`wait_for`]: Self::wait_for
+    pub fn wait_all(self) -> WaitAll<List>
+    where
+        List: list::WaitAll,
+    {
+        WaitAll {
+            fut_list: Some(self.fut_list),
+        }
+    }
+
+    /// Consumes this group and returns a future that resolves as soon as any one of the attached
+    /// background futures completes, yielding that future's output together with a new `Group` holding
+    /// the rest of the attached futures.
+    ///
+    /// If more than one future completes during the same poll, the lowest-indexed one (i.e. the one
+    /// that would be detached by index [`Z`](list::Z)) wins.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::pin::pin;
+    /// # pollster::block_on(async {
+    /// let group = async_let::Group::new();
+    ///
+    /// let f1 = pin!(async { 3 });
+    /// let (_, group) = group.attach(f1);
+    /// let f2 = pin!(async { 7 });
+    /// let (_, group) = group.attach(f2);
+    ///
+    /// // `f2` is attached last, so it races ahead of `f1` when both are immediately ready.
+    /// let (winner, _group) = group.wait_for_any().await;
+    /// assert_eq!(winner, 7);
+    /// # });
+    /// ```
+    pub fn wait_for_any<T>(self) -> WaitForAny<List, T>
+    where
+        List: Race<T>,
+    {
+        WaitForAny {
+            fut_list: Some(self.fut_list),
+            _holds_output: PhantomData,
+        }
+    }
+
+    /// Polls every not-yet-ready background future in this group once, without awaiting anything.
+    ///
+    /// This is a non-blocking alternative to [`wait_for`](Self::wait_for) for callers writing their
+    /// own `poll` implementations, who want to give the background futures a chance to make progress
+    /// against the current task's waker without being forced through an `await`.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::pin::pin;
+    /// # pollster::block_on(async {
+    /// use core::future::poll_fn;
+    ///
+    /// let group = async_let::Group::new();
+    /// let f1 = pin!(async { 3 });
+    /// let (handle, mut group) = group.attach(f1);
+    ///
+    /// poll_fn(|cx| {
+    ///     group.poll_background(cx);
+    ///     core::task::Poll::Ready(())
+    /// })
+    /// .await;
+    /// assert!(group.is_ready(&handle));
+    /// # });
+    /// ```
+    pub fn poll_background(&mut self, cx: &mut Context<'_>)
+    where
+        List: FutList,
+    {
+        self.fut_list.poll_once(cx);
+    }
+
+    /// Reports whether the future represented by `handle` has completed, without consuming it.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::pin::pin;
+    /// # pollster::block_on(async {
+    /// use core::future::poll_fn;
+    ///
+    /// let group = async_let::Group::new();
+    /// let fut = pin!(async { 10 });
+    /// let (handle, mut group) = group.attach(fut);
+    /// assert!(!group.is_ready(&handle));
+    ///
+    /// poll_fn(|cx| {
+    ///     group.poll_background(cx);
+    ///     core::task::Poll::Ready(())
+    /// })
+    /// .await;
+    /// assert!(group.is_ready(&handle));
+    /// # });
+    /// ```
+    pub fn is_ready<I, F: Future>(&self, handle: &Handle<F>) -> bool
+    where
+        List: Peek<F, I>,
+    {
+        let _ = handle;
+        self.fut_list.is_ready()
+    }
+
+    /// Detaches the future represented by `handle` only if it has already completed, returning its
+    /// output. If the future has not yet completed, this method returns the group unchanged so the
+    /// caller can check again later, without disturbing the rest of the group.
+    ///
+    /// # Example
+    /// ```
+    /// # use core::pin::pin;
+    /// # pollster::block_on(async {
+    /// use core::future::poll_fn;
+    ///
+    /// let group = async_let::Group::new();
+    /// let fut = pin!(async { 10 });
+    /// let (handle, group) = group.attach(fut);
+    ///
+    /// let mut group = match group.try_detach(&handle) {
+    ///     Ok(_) => panic!("the future has not completed yet"),
+    ///     Err(group) => group,
+    /// };
+    ///
+    /// poll_fn(|cx| {
+    ///     group.poll_background(cx);
+    ///     core::task::Poll::Ready(())
+    /// })
+    /// .await;
+    /// let (output, _group) = match group.try_detach(&handle) {
+    ///     Ok(pair) => pair,
+    ///     Err(_group) => panic!("the future has completed by now"),
+    /// };
+    /// assert_eq!(output, 10);
+    /// # });
+    /// ```
+    pub fn try_detach<I, F: Future>(
+        self,
+        handle: &Handle<F>,
+    ) -> TryDetach<F, List, List::Output>
+    where
+        List: Detach<F, I> + Peek<F, I>,
+    {
+        if !self.fut_list.is_ready() {
+            return Err(self);
+        }
+        let _ = handle;
+        match self.fut_list.detach() {
+            (ReadyOrNot::Ready(val), rest) => Ok((val, Group { fut_list: rest })),
+            (ReadyOrNot::Not(_), _) => {
+                unreachable!("Peek reported the future as ready, but detach found it pending")
+            }
+        }
+    }
 }
@@ -1,5 +1,12 @@
 use crate::{wait::DriveWaitFor, ReadyOrNot};
-use core::{future::Future, marker::PhantomData};
+#[cfg(feature = "alloc")]
+use crate::wait::PollTracked;
+use core::{
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
 
 /// Represents a typed list of no background futures.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -8,21 +15,74 @@ pub struct Empty {
 }
 
 /// Represents a typed list of one or more background futures.
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+// the `alloc`-gated waker bookkeeping holds a `Waker` and an atomic bitset, neither of which can be
+// compared, so the comparison derives only apply without that feature.
+#[cfg_attr(not(feature = "alloc"), derive(PartialEq, Eq, PartialOrd, Ord))]
+#[derive(Debug)]
 pub struct At<F: Future, Tail> {
     pub(crate) node: ReadyOrNot<F>,
     pub(crate) tail: Tail,
     // needed to tell derive macros that this type indirectly contains F::Output
     pub(crate) _holds_output: PhantomData<F::Output>,
+    #[cfg(feature = "alloc")]
+    pub(crate) wake: crate::waker::WakeTracking,
 }
 
 /// A trait representing a list of background futures.
+#[cfg(not(feature = "alloc"))]
 pub trait FutList: DriveWaitFor {}
 
+/// A trait representing a list of background futures. Also requires [`PollTracked`](crate::wait::PollTracked)
+/// and [`ListBits`] under the `alloc` feature, since every node in the list then needs to support
+/// mask-driven polling and share a single readiness bitset.
+#[cfg(feature = "alloc")]
+pub trait FutList: DriveWaitFor + crate::wait::PollTracked + ListBits {}
+
 impl FutList for Empty {}
 
 impl<F: Future + Unpin, T: FutList> FutList for At<F, T> {}
 
+/// A trait for counting the futures in a list, used to assign each newly attached future a stable
+/// bit index for the `alloc`-gated per-future waker tracking.
+#[cfg(feature = "alloc")]
+pub trait ListLen {
+    /// The number of futures currently in the list.
+    const LEN: u32;
+}
+
+#[cfg(feature = "alloc")]
+impl ListLen for Empty {
+    const LEN: u32 = 0;
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future, T: ListLen> ListLen for At<F, T> {
+    const LEN: u32 = 1 + T::LEN;
+}
+
+/// A trait for retrieving the readiness bitset shared by every future in a list, used by
+/// [`Group::attach`](crate::Group::attach) so that newly attached futures share the same bitset as
+/// their tail.
+#[cfg(feature = "alloc")]
+pub trait ListBits {
+    /// Returns the readiness bitset shared by this list, creating a fresh one if the list is empty.
+    fn bits(&self) -> alloc::sync::Arc<crate::waker::ReadinessBits>;
+}
+
+#[cfg(feature = "alloc")]
+impl ListBits for Empty {
+    fn bits(&self) -> alloc::sync::Arc<crate::waker::ReadinessBits> {
+        alloc::sync::Arc::new(crate::waker::ReadinessBits::new())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future, T> ListBits for At<F, T> {
+    fn bits(&self) -> alloc::sync::Arc<crate::waker::ReadinessBits> {
+        self.wake.bits.clone()
+    }
+}
+
 /// A marker type used for indexing futures in a group. This type represents the first future in a group.
 pub struct Z(());
 
@@ -60,7 +120,249 @@ where
                 node: self.node,
                 tail,
                 _holds_output: PhantomData,
+                #[cfg(feature = "alloc")]
+                wake: self.wake,
             },
         )
     }
 }
+
+/// A trait that defines the operation of checking whether the future of type `F` at index `I` has
+/// completed, without consuming it. This is the recursive machinery behind
+/// [`Group::is_ready`](crate::Group::is_ready) and [`Group::try_detach`](crate::Group::try_detach).
+pub trait Peek<F: Future, I> {
+    /// Reports whether the future at index `I` has completed.
+    fn is_ready(&self) -> bool;
+}
+
+impl<F: Future, T> Peek<F, Z> for At<F, T> {
+    #[inline]
+    fn is_ready(&self) -> bool {
+        matches!(self.node, ReadyOrNot::Ready(_))
+    }
+}
+
+impl<F: Future, I, H: Future, T: Peek<F, I>> Peek<F, S<I>> for At<H, T> {
+    #[inline]
+    fn is_ready(&self) -> bool {
+        self.tail.is_ready()
+    }
+}
+
+/// A trait that defines the operation of driving every future in a list to completion. This is the
+/// recursive machinery behind [`Group::wait_all`](crate::Group::wait_all).
+pub trait WaitAll: FutList {
+    /// The nested tuple of outputs produced once every future in the list has completed, ordered
+    /// from the least recently attached future to the most recently attached (i.e. attach order).
+    type Output;
+
+    /// Polls every not-yet-ready node in the list once, returning `Poll::Ready(())` once every node
+    /// has transitioned to [`ReadyOrNot::Ready`].
+    fn poll_wait_all(&mut self, cx: &mut Context<'_>) -> Poll<()>;
+
+    /// Consumes the list, extracting the output of each future. Must only be called once
+    /// `poll_wait_all` has reported `Poll::Ready(())`.
+    fn take_output(self) -> Self::Output;
+}
+
+impl WaitAll for Empty {
+    type Output = ();
+
+    #[inline]
+    fn poll_wait_all(&mut self, _cx: &mut Context<'_>) -> Poll<()> {
+        Poll::Ready(())
+    }
+
+    #[inline]
+    fn take_output(self) -> Self::Output {}
+}
+
+impl<F: Future + Unpin, T: WaitAll> WaitAll for At<F, T> {
+    type Output = (T::Output, F::Output);
+
+    fn poll_wait_all(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        let At { node, tail, .. } = self;
+        if let ReadyOrNot::Not(fut) = node {
+            if let Poll::Ready(val) = Pin::new(fut).poll(cx) {
+                *node = ReadyOrNot::Ready(val);
+            }
+        }
+        let node_ready = matches!(node, ReadyOrNot::Ready(_));
+        let tail_ready = tail.poll_wait_all(cx).is_ready();
+        if node_ready && tail_ready {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+
+    fn take_output(self) -> Self::Output {
+        let At { node, tail, .. } = self;
+        let val = match node {
+            ReadyOrNot::Ready(val) => val,
+            // `take_output` is only ever called after `poll_wait_all` has reported every node ready.
+            ReadyOrNot::Not(_) => unreachable!("wait_all output taken before the future completed"),
+        };
+        (tail.take_output(), val)
+    }
+}
+
+/// The group that results from racing the futures of an `At<F, Tail>` list, depending on whether this
+/// node's future or one of `Tail`'s futures completed first. This is the recursive machinery behind
+/// [`Group::wait_for_any`](crate::Group::wait_for_any).
+pub enum Raced<F: Future, Tail: RaceTail<F::Output>> {
+    /// This node's future completed first and has been removed from the group.
+    Head(Tail),
+    /// A future further down the list completed first; this node is kept, still pending.
+    Tail(At<F, Tail::Remaining>),
+}
+
+/// A trait that defines the operation of racing a homogeneously-typed, non-empty list of futures,
+/// resolving as soon as any one of them completes. Only implemented for [`At`], since a `Group` needs
+/// at least one attached future in order to race.
+pub trait Race<T>: FutList {
+    /// The group that remains after the winning future is removed.
+    type Remaining;
+
+    /// Polls each not-yet-ready node in turn. If a node completes, it is removed from the list and
+    /// its output is returned alongside the remaining list. If no node completes, `self` is returned
+    /// unchanged so the caller can poll again later. When multiple nodes complete during the same
+    /// poll, the lowest-indexed node (the one closest to the head) wins.
+    fn poll_race(self, cx: &mut Context<'_>) -> Result<(T, Self::Remaining), Self>
+    where
+        Self: Sized;
+}
+
+/// Helper trait implemented for every possible tail of a [`Race`]d list, including [`Empty`]. This
+/// lets the base case (no more futures to race) be handled without conflicting with the general
+/// recursive case, the way [`Detach`]'s [`Z`]/[`S`] markers disambiguate its recursion.
+pub trait RaceTail<T>: FutList {
+    /// The tail that remains after the winning future is removed, if this tail contains the winner.
+    type Remaining;
+
+    /// Polls this tail for a winner. Returns `Err(self)` unchanged if this tail is [`Empty`], since an
+    /// empty tail can never contain a winning future.
+    fn poll_race_tail(self, cx: &mut Context<'_>) -> Result<(T, Self::Remaining), Self>
+    where
+        Self: Sized;
+}
+
+impl<T> RaceTail<T> for Empty {
+    type Remaining = Empty;
+
+    #[inline]
+    fn poll_race_tail(self, _cx: &mut Context<'_>) -> Result<(T, Empty), Self> {
+        Err(self)
+    }
+}
+
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> RaceTail<F::Output> for At<F, Tail> {
+    type Remaining = Raced<F, Tail>;
+
+    #[inline]
+    fn poll_race_tail(self, cx: &mut Context<'_>) -> Result<(F::Output, Self::Remaining), Self> {
+        self.poll_race(cx)
+    }
+}
+
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> Race<F::Output> for At<F, Tail> {
+    type Remaining = Raced<F, Tail>;
+
+    fn poll_race(mut self, cx: &mut Context<'_>) -> Result<(F::Output, Self::Remaining), Self> {
+        match self.node {
+            ReadyOrNot::Not(ref mut fut) => {
+                // under `alloc`, poll through the node's personal waker (as `poll_tracked` does), so a
+                // node left pending here still sets its readiness bit when it later wakes.
+                #[cfg(feature = "alloc")]
+                let poll = Pin::new(fut).poll(&mut Context::from_waker(self.wake.waker_or_init(cx.waker())));
+                #[cfg(not(feature = "alloc"))]
+                let poll = Pin::new(fut).poll(cx);
+                if let Poll::Ready(val) = poll {
+                    return Ok((val, Raced::Head(self.tail)));
+                }
+            }
+            // This node already completed without being detached, e.g. a prior `wait_for` or
+            // `poll_background` drove it to completion opportunistically. It
+            // still wins immediately rather than being polled again, consistent with "lowest index
+            // wins": a node found ready here is always found before its tail is even considered.
+            ReadyOrNot::Ready(_) => match self.node {
+                ReadyOrNot::Ready(val) => return Ok((val, Raced::Head(self.tail))),
+                ReadyOrNot::Not(_) => unreachable!("just matched Ready above"),
+            },
+        }
+        match self.tail.poll_race_tail(cx) {
+            Ok((val, remaining)) => Ok((
+                val,
+                Raced::Tail(At {
+                    node: self.node,
+                    tail: remaining,
+                    _holds_output: PhantomData,
+                    #[cfg(feature = "alloc")]
+                    wake: self.wake,
+                }),
+            )),
+            Err(tail) => Err(At {
+                node: self.node,
+                tail,
+                _holds_output: PhantomData,
+                #[cfg(feature = "alloc")]
+                wake: self.wake,
+            }),
+        }
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> DriveWaitFor for Raced<F, Tail>
+where
+    Tail::Remaining: FutList,
+{
+    fn poll_once(&mut self, cx: &mut Context<'_>) {
+        match self {
+            Raced::Head(tail) => tail.poll_once(cx),
+            Raced::Tail(at) => at.poll_once(cx),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> DriveWaitFor for Raced<F, Tail>
+where
+    Tail::Remaining: FutList,
+{
+    fn poll_once(&mut self, cx: &mut Context<'_>) {
+        let mask = self.bits().take();
+        self.poll_tracked(mask, cx);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> crate::wait::PollTracked for Raced<F, Tail>
+where
+    Tail::Remaining: FutList,
+{
+    fn poll_tracked(&mut self, mask: usize, cx: &mut Context<'_>) {
+        match self {
+            Raced::Head(tail) => tail.poll_tracked(mask, cx),
+            Raced::Tail(at) => at.poll_tracked(mask, cx),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> ListBits for Raced<F, Tail>
+where
+    Tail::Remaining: FutList,
+{
+    fn bits(&self) -> alloc::sync::Arc<crate::waker::ReadinessBits> {
+        match self {
+            Raced::Head(tail) => tail.bits(),
+            Raced::Tail(at) => at.bits(),
+        }
+    }
+}
+
+impl<F: Future + Unpin, Tail: RaceTail<F::Output>> FutList for Raced<F, Tail> where
+    Tail::Remaining: FutList
+{
+}
@@ -43,11 +43,68 @@ mod private {
     }
 }
 
+pin_project_lite::pin_project! {
+    /// Future type for the [`Group::wait_all`] method.
+    ///
+    /// [`wait_all`]: super::Group::wait_all
+    #[derive(Debug)]
+    pub struct WaitAll<List> {
+        pub(crate) fut_list: Option<List>,
+    }
+}
+
+impl<List: crate::list::WaitAll> Future for WaitAll<List> {
+    type Output = List::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let list = this
+            .fut_list
+            .as_mut()
+            .expect("WaitAll polled after it already completed");
+        match list.poll_wait_all(cx) {
+            Poll::Ready(()) => Poll::Ready(this.fut_list.take().unwrap().take_output()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pin_project_lite::pin_project! {
+    /// Future type for the [`Group::wait_for_any`] method.
+    ///
+    /// [`wait_for_any`]: super::Group::wait_for_any
+    #[derive(Debug)]
+    pub struct WaitForAny<List, T> {
+        pub(crate) fut_list: Option<List>,
+        pub(crate) _holds_output: core::marker::PhantomData<fn() -> T>,
+    }
+}
+
+impl<List: crate::list::Race<T>, T> Future for WaitForAny<List, T> {
+    type Output = (T, crate::Group<List::Remaining>);
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let list = this
+            .fut_list
+            .take()
+            .expect("WaitForAny polled after it already completed");
+        match list.poll_race(cx) {
+            Ok((val, remaining)) => Poll::Ready((val, crate::Group { fut_list: remaining })),
+            Err(list) => {
+                *this.fut_list = Some(list);
+                Poll::Pending
+            }
+        }
+    }
+}
+
 impl DriveWaitFor for Empty {
     #[inline]
     fn poll_once(&mut self, _cx: &mut Context<'_>) {}
 }
 
+#[cfg(not(feature = "alloc"))]
 impl<F: Future + Unpin, T: DriveWaitFor> DriveWaitFor for At<F, T>
 {
     fn poll_once(&mut self, cx: &mut Context<'_>) {
@@ -60,3 +117,52 @@ impl<F: Future + Unpin, T: DriveWaitFor> DriveWaitFor for At<F, T>
         tail.poll_once(cx);
     }
 }
+
+/// Helper trait that recurses through a list using an already-`take`n readiness bitmask, rather than
+/// re-reading the shared bitset at every node. This is what lets [`At::poll_once`](DriveWaitFor::poll_once)
+/// only poll the futures whose bit was actually set.
+///
+/// This is public only because it is a supertrait of the public [`FutList`](crate::list::FutList);
+/// it has no meaningful impls outside this crate's own list types.
+#[cfg(feature = "alloc")]
+pub trait PollTracked {
+    #[doc(hidden)]
+    fn poll_tracked(&mut self, mask: usize, cx: &mut Context<'_>);
+}
+
+#[cfg(feature = "alloc")]
+impl PollTracked for Empty {
+    #[inline]
+    fn poll_tracked(&mut self, _mask: usize, _cx: &mut Context<'_>) {}
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future + Unpin, T: PollTracked> PollTracked for At<F, T> {
+    fn poll_tracked(&mut self, mask: usize, cx: &mut Context<'_>) {
+        if let ReadyOrNot::Not(fut) = &mut self.node {
+            // a node must be polled if it has never been polled (no personal waker registered yet),
+            // if its bit is set, or if the parent waker has changed since it last registered one —
+            // the only way a future can learn of a new waker is to be polled with it, so skipping a
+            // node whose parent waker changed would leave it forever forwarding wakes to a stale one.
+            let needs_poll = self.wake.personal_waker.is_none()
+                || crate::waker::ReadinessBits::is_set(mask, self.wake.index)
+                || self.wake.is_stale(cx.waker());
+            if needs_poll {
+                let waker = self.wake.waker_or_init(cx.waker());
+                let mut node_cx = Context::from_waker(waker);
+                if let Poll::Ready(val) = Pin::new(fut).poll(&mut node_cx) {
+                    self.node = ReadyOrNot::Ready(val);
+                }
+            }
+        }
+        self.tail.poll_tracked(mask, cx);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<F: Future + Unpin, T: DriveWaitFor + PollTracked> DriveWaitFor for At<F, T> {
+    fn poll_once(&mut self, cx: &mut Context<'_>) {
+        let mask = self.wake.bits.take();
+        self.poll_tracked(mask, cx);
+    }
+}
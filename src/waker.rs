@@ -0,0 +1,123 @@
+//! Per-future waker tracking, gated behind the `alloc` feature.
+//!
+//! Without this feature, [`DriveWaitFor::poll_once`](crate::wait::DriveWaitFor) re-polls every
+//! not-yet-ready node in a group's list on every wakeup. The types here let a group instead hand
+//! each background future its own [`Waker`] that marks a bit in a small shared bitset, so a later
+//! wakeup only needs to re-poll the futures that actually made progress.
+
+use alloc::sync::Arc;
+use alloc::task::Wake;
+use core::sync::atomic::{AtomicUsize, Ordering};
+use core::task::Waker;
+
+/// A small, shared readiness bitset. Bit `i` is set once the background future at index `i` wakes
+/// its personal waker, and cleared the next time the bitset is [`take`](Self::take)n.
+///
+/// This is public only because [`ListBits`](crate::list::ListBits), a supertrait of the public
+/// [`FutList`](crate::list::FutList), names it in its return type; it has no public constructor or
+/// accessors outside this crate.
+#[derive(Debug, Default)]
+pub struct ReadinessBits(AtomicUsize);
+
+impl ReadinessBits {
+    pub(crate) fn new() -> Self {
+        Self(AtomicUsize::new(0))
+    }
+
+    /// Sets bit `index`, or does nothing if `index` is too wide for this machine word to track (see
+    /// [`Self::is_set`] for how that case is surfaced to callers instead of panicking or wrapping).
+    fn set(&self, index: u32) {
+        if let Some(bit) = 1usize.checked_shl(index) {
+            self.0.fetch_or(bit, Ordering::Release);
+        }
+    }
+
+    /// Reads and clears the bitset, returning the bits that were set since the last call.
+    pub(crate) fn take(&self) -> usize {
+        self.0.swap(0, Ordering::AcqRel)
+    }
+
+    /// Reports whether bit `index` is set in `mask`, a value previously returned by [`take`](Self::take).
+    ///
+    /// A single `AtomicUsize` can only track `usize::BITS` futures (64 on common targets). Beyond
+    /// that, a future's index can never be represented in the bitset, so it is always reported as
+    /// set: such futures simply lose the "only poll what was woken" optimization and fall back to
+    /// being polled on every wakeup, rather than panicking (shifting by `>= usize::BITS` would) or
+    /// wrapping and aliasing onto another future's bit.
+    pub(crate) fn is_set(mask: usize, index: u32) -> bool {
+        match 1usize.checked_shl(index) {
+            Some(bit) => mask & bit != 0,
+            None => true,
+        }
+    }
+}
+
+/// The personal waker handed to a single background future. Waking it marks this future's bit in
+/// the shared [`ReadinessBits`] before forwarding the wake to the parent task's waker.
+struct NodeWaker {
+    bits: Arc<ReadinessBits>,
+    index: u32,
+    parent: Waker,
+}
+
+impl NodeWaker {
+    pub(crate) fn new_waker(bits: Arc<ReadinessBits>, index: u32, parent: Waker) -> Waker {
+        Waker::from(Arc::new(Self { bits, index, parent }))
+    }
+}
+
+impl Wake for NodeWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.bits.set(self.index);
+        self.parent.wake_by_ref();
+    }
+}
+
+/// The alloc-backed waker bookkeeping an [`At`](crate::list::At) node carries: the bitset shared
+/// with every other node in the same list, this node's own bit index, and its lazily built personal
+/// waker together with the parent waker it was last built against.
+#[derive(Debug)]
+pub(crate) struct WakeTracking {
+    pub(crate) bits: Arc<ReadinessBits>,
+    pub(crate) index: u32,
+    pub(crate) personal_waker: Option<Waker>,
+    /// The parent waker `personal_waker` currently forwards to. Compared against on every poll so a
+    /// parent waker change (which `Future::poll`'s contract allows at any time) is noticed even on
+    /// polls where this node's bit wasn't set and it would otherwise be skipped entirely.
+    last_parent: Option<Waker>,
+}
+
+impl WakeTracking {
+    pub(crate) fn new(bits: Arc<ReadinessBits>, index: u32) -> Self {
+        Self {
+            bits,
+            index,
+            personal_waker: None,
+            last_parent: None,
+        }
+    }
+
+    /// Reports whether `parent` differs from the waker `personal_waker` currently forwards to, i.e.
+    /// whether the node must be re-polled so it can learn about it, even if its readiness bit isn't set.
+    pub(crate) fn is_stale(&self, parent: &Waker) -> bool {
+        match &self.last_parent {
+            Some(last_parent) => !last_parent.will_wake(parent),
+            None => true,
+        }
+    }
+
+    /// Returns this node's personal waker, building it against `parent` the first time it's needed
+    /// and rebuilding it whenever `parent` is a different waker than the one it was last built with,
+    /// per the `Future::poll` contract that a future must wake the most recently supplied context.
+    pub(crate) fn waker_or_init(&mut self, parent: &Waker) -> &Waker {
+        if self.is_stale(parent) {
+            self.personal_waker = Some(NodeWaker::new_waker(self.bits.clone(), self.index, parent.clone()));
+            self.last_parent = Some(parent.clone());
+        }
+        self.personal_waker.as_ref().expect("just initialized above")
+    }
+}